@@ -0,0 +1,357 @@
+//! Shared CREATE2 vanity-address search core.
+//!
+//! `vanity-generator`, `vrf_integrator_vanity` and `vrf_vanity` each used to
+//! reimplement `compute_create2_address`, the rayon fan-out and the atomic
+//! attempt counters with subtly different bugs (random vs. sequential
+//! salts, different progress reporting, even a placeholder bytecode-hash
+//! function). This crate factors that core into one place: a
+//! [`VanitySearcher`] that hashes a reusable 85-byte preimage buffer per
+//! worker thread and takes a pluggable [`Matcher`] and [`SaltStrategy`].
+//! `vanity-generator` and the random-salt path of `vrf_integrator_vanity`
+//! search through it; `vrf_vanity` (via [`VanitySearcher::address_for_salt`])
+//! and the passphrase path of `vrf_integrator_vanity` run their own search
+//! loops.
+
+use rayon::prelude::*;
+use sha3::{Digest, Keccak256};
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+pub type Address20 = [u8; 20];
+pub type Salt = [u8; 32];
+
+/// A predicate over a candidate CREATE2 address. Implemented for any
+/// `Fn(&Address20) -> bool` closure so callers don't need a named type for
+/// simple one-off checks, and for `Vec<Box<dyn Matcher>>` so a search can
+/// require several modes at once (prefix/suffix, checksum, contains,
+/// leading-zero-bytes, ...) to all match.
+pub trait Matcher: Send + Sync {
+    fn matches(&self, address: &Address20) -> bool;
+}
+
+impl<F: Fn(&Address20) -> bool + Send + Sync> Matcher for F {
+    fn matches(&self, address: &Address20) -> bool {
+        self(address)
+    }
+}
+
+impl Matcher for Vec<Box<dyn Matcher>> {
+    fn matches(&self, address: &Address20) -> bool {
+        self.iter().all(|m| m.matches(address))
+    }
+}
+
+/// How successive salts are produced during a search.
+pub enum SaltStrategy {
+    /// Scan a contiguous range of big-endian sequential salts starting at
+    /// `start`, one per attempt.
+    Sequential { start: u64 },
+    /// Draw independent random 32-byte salts.
+    Random,
+}
+
+/// A single found (or checkpointed) result.
+#[derive(Debug, Clone, Copy)]
+pub struct SearchResult {
+    pub salt: Salt,
+    pub address: Address20,
+    pub attempts: u64,
+}
+
+/// Periodically-persisted progress for a sequential search.
+///
+/// A sequential search partitions `0..max_attempts` across `threads`
+/// workers, each scanning its own contiguous slice — so a single scalar
+/// can't represent the combined frontier; `highest_salts[i]` is the
+/// highest salt worker `i` had scanned as of the last save, `None` if
+/// that worker hadn't reported yet. Resuming is only meaningful with the
+/// same `--threads` value used to create the checkpoint, since the slice
+/// boundaries are derived from the thread count.
+#[derive(Debug, Clone, Default)]
+pub struct Checkpoint {
+    pub highest_salts: Vec<Option<u64>>,
+    pub attempts: u64,
+}
+
+impl Checkpoint {
+    pub fn load(path: &Path) -> Option<Self> {
+        let text = std::fs::read_to_string(path).ok()?;
+        let mut sections = text.trim().split(';');
+        let attempts = sections.next()?.parse().ok()?;
+        let highest_salts: Option<Vec<Option<u64>>> = sections
+            .next()?
+            .split(',')
+            .map(|s| if s.is_empty() { Some(None) } else { s.parse().ok().map(Some) })
+            .collect();
+        Some(Checkpoint { highest_salts: highest_salts?, attempts })
+    }
+
+    /// Writes to a temp file and renames over `path`, so a reader (or a
+    /// crash mid-write) never observes a half-written, unparseable file.
+    /// This alone doesn't make concurrent writers safe — `search` below
+    /// also funnels every save through one `Mutex` so two threads' writes
+    /// can't race each other in the first place.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let salts: Vec<String> =
+            self.highest_salts.iter().map(|s| s.map(|v| v.to_string()).unwrap_or_default()).collect();
+        let contents = format!("{};{}", self.attempts, salts.join(","));
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, contents)?;
+        std::fs::rename(&tmp_path, path)
+    }
+
+    /// Salt to resume a sequential search from, or `None` if the
+    /// checkpoint has no safe resume point (a worker never reported, so
+    /// its slice's progress is unknown).
+    ///
+    /// Uses the *lowest* recorded salt across all workers, not the
+    /// highest: resuming from any worker's own frontier would silently
+    /// skip the unscanned tail of a slower worker's slice. Resuming from
+    /// the minimum instead re-scans some already-covered range but never
+    /// skips a salt that hadn't been checked yet.
+    pub fn resume_start(&self) -> Option<u64> {
+        let salts: Option<Vec<u64>> = self.highest_salts.iter().copied().collect();
+        salts?.into_iter().min().map(|m| m + 1)
+    }
+}
+
+/// Searches for a CREATE2 salt producing an address that satisfies a
+/// [`Matcher`], fanning out across `threads` rayon workers.
+pub struct VanitySearcher {
+    factory: Address20,
+    bytecode_hash: [u8; 32],
+    threads: usize,
+}
+
+impl VanitySearcher {
+    pub fn new(factory: Address20, bytecode_hash: [u8; 32], threads: usize) -> Self {
+        Self { factory, bytecode_hash, threads }
+    }
+
+    /// Build the constant 85-byte preimage prefix/suffix
+    /// (`0xff || factory(20) || 0-salt(32) || bytecode_hash(32)`) once per
+    /// worker thread; only the 32 salt bytes need overwriting per attempt.
+    fn init_preimage(&self) -> [u8; 85] {
+        let mut preimage = [0u8; 85];
+        preimage[0] = 0xff;
+        preimage[1..21].copy_from_slice(&self.factory);
+        preimage[53..85].copy_from_slice(&self.bytecode_hash);
+        preimage
+    }
+
+    /// Compute the CREATE2 address for a specific salt (used for one-off
+    /// verification, e.g. a `recover` subcommand re-deriving a known salt).
+    pub fn address_for_salt(&self, salt: &Salt) -> Address20 {
+        let mut preimage = self.init_preimage();
+        preimage[21..53].copy_from_slice(salt);
+        hash_preimage(&preimage)
+    }
+
+    /// Run the search. Progress is reported via `on_progress(total_attempts)`
+    /// every `progress_every` attempts; for `Sequential` searches a
+    /// checkpoint is also persisted to `checkpoint_path` (if given) at the
+    /// same cadence.
+    pub fn search(
+        &self,
+        strategy: SaltStrategy,
+        max_attempts: u64,
+        matcher: &dyn Matcher,
+        progress_every: u64,
+        checkpoint_path: Option<&Path>,
+        on_progress: impl Fn(u64) + Sync,
+    ) -> Option<SearchResult> {
+        let found = Arc::new(AtomicBool::new(false));
+        let attempts = Arc::new(AtomicU64::new(0));
+        let per_thread = (max_attempts / self.threads as u64).max(1);
+        // One highest-salt slot per worker, all saves funneled through a
+        // single mutex so concurrent threads can't interleave writes to
+        // the same checkpoint file.
+        let checkpoint = Arc::new(Mutex::new(Checkpoint { highest_salts: vec![None; self.threads], attempts: 0 }));
+
+        let result = (0..self.threads)
+            .into_par_iter()
+            .map(|thread_id| {
+                let mut preimage = self.init_preimage();
+                // One hasher reused for every attempt on this thread via
+                // `finalize_reset`, instead of constructing (and heap-
+                // allocating behind) a new one per attempt.
+                let mut hasher = Keccak256::new();
+                // Sequential ranges are partitioned per thread so no two
+                // threads ever hash the same salt; random draws don't need
+                // partitioning since the salt space is 2^256.
+                let sequential_start = match strategy {
+                    SaltStrategy::Sequential { start } => Some(start + thread_id as u64 * per_thread),
+                    SaltStrategy::Random => None,
+                };
+
+                for i in 0..per_thread {
+                    if found.load(Ordering::Relaxed) {
+                        return None;
+                    }
+
+                    let salt = match sequential_start {
+                        Some(base) => {
+                            let mut salt = [0u8; 32];
+                            salt[24..].copy_from_slice(&(base + i).to_be_bytes());
+                            salt
+                        }
+                        None => rand::random(),
+                    };
+                    preimage[21..53].copy_from_slice(&salt);
+                    let address = hash_preimage_reuse(&mut hasher, &preimage);
+
+                    let total = attempts.fetch_add(1, Ordering::Relaxed) + 1;
+                    if total % progress_every == 0 {
+                        on_progress(total);
+                        if let (Some(path), Some(base)) = (checkpoint_path, sequential_start) {
+                            let mut cp = checkpoint.lock().unwrap();
+                            cp.highest_salts[thread_id] = Some(base + i);
+                            cp.attempts = total;
+                            let _ = cp.save(path);
+                        }
+                    }
+
+                    if matcher.matches(&address) {
+                        found.store(true, Ordering::Relaxed);
+                        return Some(SearchResult { salt, address, attempts: total });
+                    }
+                }
+                None
+            })
+            .find_any(|r| r.is_some());
+
+        result.flatten()
+    }
+}
+
+fn hash_preimage(preimage: &[u8; 85]) -> Address20 {
+    let hash = Keccak256::digest(preimage);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// Hashes `preimage` with an already-constructed `hasher`, resetting it in
+/// place afterwards so the caller can reuse it for the next attempt instead
+/// of constructing (and dropping) a fresh `Keccak256` per attempt. An
+/// earlier version of this hot loop instead batched several preimages into
+/// a `Vec<Keccak256>` per round to try to get independent hashes computing
+/// concurrently — but that reintroduced a heap allocation on every round,
+/// the exact per-attempt allocation this buffer-reuse approach exists to
+/// avoid, so it was dropped in favor of this simpler reset-in-place loop.
+fn hash_preimage_reuse(hasher: &mut Keccak256, preimage: &[u8; 85]) -> Address20 {
+    Digest::update(hasher, preimage);
+    let hash = hasher.finalize_reset();
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// Compute the EIP-55 checksummed (mixed-case) hex representation of an
+/// address, shared by every matcher that needs checksum-aware matching.
+pub fn to_checksum_hex(address: &Address20) -> String {
+    let addr_hex = hex::encode(address);
+    let hash = Keccak256::digest(addr_hex.as_bytes());
+
+    let mut checksummed = String::with_capacity(42);
+    checksummed.push_str("0x");
+    for (i, c) in addr_hex.chars().enumerate() {
+        let hash_byte = hash[i / 2];
+        let nibble = if i % 2 == 0 { hash_byte >> 4 } else { hash_byte & 0x0f };
+        if c.is_ascii_alphabetic() && nibble >= 8 {
+            checksummed.push(c.to_ascii_uppercase());
+        } else {
+            checksummed.push(c);
+        }
+    }
+    checksummed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequential_search_finds_zero_salt_match() {
+        let factory = [0u8; 20];
+        let bytecode_hash = [0u8; 32];
+        let searcher = VanitySearcher::new(factory, bytecode_hash, 1);
+        let target = searcher.address_for_salt(&[0u8; 32]);
+
+        let result = searcher
+            .search(
+                SaltStrategy::Sequential { start: 0 },
+                10,
+                &(move |addr: &Address20| *addr == target),
+                1_000,
+                None,
+                |_| {},
+            )
+            .expect("salt 0 should be found immediately");
+
+        assert_eq!(result.salt, [0u8; 32]);
+        assert_eq!(result.address, target);
+    }
+
+    #[test]
+    fn checksum_hex_matches_known_vector() {
+        // EIP-55 test vector.
+        let address = hex::decode("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").unwrap();
+        let mut addr20 = [0u8; 20];
+        addr20.copy_from_slice(&address);
+        assert_eq!(to_checksum_hex(&addr20), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+    }
+
+    #[test]
+    fn checkpoint_round_trips_through_disk() {
+        let dir = std::env::temp_dir().join("create2-vanity-checkpoint-test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("checkpoint.txt");
+
+        let checkpoint = Checkpoint { highest_salts: vec![Some(42), Some(50), None], attempts: 1_000 };
+        checkpoint.save(&path).unwrap();
+        let loaded = Checkpoint::load(&path).unwrap();
+
+        assert_eq!(loaded.highest_salts, vec![Some(42), Some(50), None]);
+        assert_eq!(loaded.attempts, 1_000);
+    }
+
+    #[test]
+    fn resume_start_uses_minimum_across_threads() {
+        let checkpoint = Checkpoint { highest_salts: vec![Some(42), Some(10), Some(99)], attempts: 1_000 };
+        assert_eq!(checkpoint.resume_start(), Some(11));
+    }
+
+    #[test]
+    fn resume_start_is_none_when_a_thread_never_reported() {
+        let checkpoint = Checkpoint { highest_salts: vec![Some(42), None], attempts: 1_000 };
+        assert_eq!(checkpoint.resume_start(), None);
+    }
+
+    #[test]
+    fn reused_hasher_matches_fresh_hasher_per_attempt() {
+        let searcher = VanitySearcher::new([0xAB; 20], [0xCD; 32], 1);
+        let mut hasher = Keccak256::new();
+
+        for salt_byte in 0..7u8 {
+            let mut preimage = [0u8; 85];
+            preimage[0] = 0xff;
+            preimage[1..21].copy_from_slice(&[0xAB; 20]);
+            preimage[21..53].copy_from_slice(&[salt_byte; 32]);
+            preimage[53..85].copy_from_slice(&[0xCD; 32]);
+
+            assert_eq!(hash_preimage_reuse(&mut hasher, &preimage), hash_preimage(&preimage));
+        }
+        // Sanity: matches the one-at-a-time API too.
+        assert_eq!(hash_preimage_reuse(&mut hasher, &{
+            let mut preimage = [0u8; 85];
+            preimage[0] = 0xff;
+            preimage[1..21].copy_from_slice(&[0xAB; 20]);
+            preimage[21..53].copy_from_slice(&[3u8; 32]);
+            preimage[53..85].copy_from_slice(&[0xCD; 32]);
+            preimage
+        }), searcher.address_for_salt(&[3u8; 32]));
+    }
+}
@@ -4,43 +4,74 @@ use ethers::prelude::*;
 use ethers::types::transaction::eip2718::TypedTransaction;
 use ethers::utils::{id, keccak256};
 use sha3::Digest;
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 
 #[derive(Parser, Debug)]
-#[command(author, version, about = "Deploy veDRAGON via CREATE2FactoryWithOwnership")] 
+#[command(author, version, about = "Deploy veDRAGON via CREATE2FactoryWithOwnership on every configured chain, verifying address parity")]
 struct Args {
-    /// RPC URL
-    #[arg(long)]
-    rpc: String,
+    /// RPC URLs to deploy to, one per chain (repeat the flag or comma-separate)
+    #[arg(long, required = true, value_delimiter = ',')]
+    rpc: Vec<String>,
 
-    /// Deployer private key (hex, with or without 0x)
+    /// Deployer private key (hex, with or without 0x) - same key used on every chain
     #[arg(long)]
     pk: String,
 
-    /// CREATE2 factory address
+    /// CREATE2 factory address, assumed identical on every chain unless
+    /// overridden with --factory-override
     #[arg(long, default_value = "0xAA28020DDA6b954D16208eccF873D79AC6533833")]
     factory: String,
 
-    /// Salt to use (0x-prefixed 32-byte hex)
+    /// Per-chain factory overrides, formatted `chain_id=address`, comma-separated
+    #[arg(long, value_delimiter = ',')]
+    factory_override: Vec<String>,
+
+    /// Per-chain gas limit overrides, formatted `chain_id=gas_limit`, comma-separated
+    #[arg(long, value_delimiter = ',')]
+    gas_override: Vec<String>,
+
+    /// Salt to use (0x-prefixed 32-byte hex), identical across all chains
     #[arg(long)]
     salt: String,
 }
 
+/// A chain we're about to deploy to, paired with a connected signer.
+struct ChainTarget {
+    chain_id: u64,
+    rpc: String,
+    factory: Address,
+    gas_override: Option<U256>,
+    client: Arc<SignerMiddleware<Provider<Http>, LocalWallet>>,
+}
+
+/// Parse `chain_id=value` pairs (as used by --factory-override/--gas-override)
+/// into a lookup keyed by chain id.
+fn parse_chain_overrides(pairs: &[String]) -> HashMap<u64, String> {
+    pairs
+        .iter()
+        .map(|pair| {
+            let (chain_id, value) = pair
+                .split_once('=')
+                .unwrap_or_else(|| panic!("override '{pair}' must be formatted chain_id=value"));
+            (
+                chain_id.parse().unwrap_or_else(|_| panic!("invalid chain id in override '{pair}'")),
+                value.to_string(),
+            )
+        })
+        .collect()
+}
+
 #[tokio::main]
 async fn main() -> eyre::Result<()> {
     let args = Args::parse();
 
-    // Provider + signer
-    let provider = Provider::<Http>::try_from(args.rpc.clone())?.interval(std::time::Duration::from_millis(2000));
-    let chain_id = provider.get_chainid().await?.as_u64();
-
     let pk_clean = args.pk.trim_start_matches("0x");
-    let wallet = LocalWallet::from_str(pk_clean)?.with_chain_id(chain_id);
-    let signer = SignerMiddleware::new(provider, wallet);
-    let client = Arc::new(signer);
-
-    let factory: Address = args.factory.parse()?;
+    let default_factory: Address = args.factory.parse()?;
+    let salt_h: H256 = args.salt.parse()?;
+    let factory_overrides = parse_chain_overrides(&args.factory_override);
+    let gas_overrides = parse_chain_overrides(&args.gas_override);
 
     // Build veDRAGON init code: creationCode + abi.encode("Voting Escrow DRAGON", "veDRAGON")
     // creationCode via foundry inspection is complex from Rust; instead reconstruct via known deployed out artifact path when present
@@ -54,43 +85,114 @@ async fn main() -> eyre::Result<()> {
     }
     let init_bytes = hex::decode(&init_clean).expect("invalid init code hex");
 
-    // Sanity log
+    // The predicted address must hold across every chain in the list -
+    // that's the whole point of an omnichain CREATE2 deploy. It is
+    // recomputed per chain from that chain's configured factory address;
+    // divergence (e.g. a --factory-override with a different address)
+    // fails the run before anything is broadcast. The factory contract's
+    // on-chain code is not inspected.
     let bytecode_hash = H256::from_slice(keccak256(&init_bytes).as_slice());
     println!("Init code hash: 0x{}", hex::encode(bytecode_hash.as_bytes()));
 
-    // Prepare call data for factory.deploy(bytes,bytes32,string)
-    // function deploy(bytes memory initCode, bytes32 salt, string memory name)
-    let salt_h: H256 = args.salt.parse()?;
     let deploy_selector = id("deploy(bytes,bytes32,string)")[..4].to_vec();
     let encoded = encode(&[
-        Token::Bytes(init_bytes),
+        Token::Bytes(init_bytes.clone()),
         Token::FixedBytes(salt_h.as_bytes().to_vec()),
         Token::String("veDRAGON".to_string()),
     ]);
     let data = [deploy_selector, encoded].concat();
 
-    // Estimate and send tx
-    let mut tx: TypedTransaction = TransactionRequest::new().to(factory).data(data).into();
-    let gas = client.estimate_gas(&tx, None).await.unwrap_or_else(|_| U256::from(3_000_000u64));
-    tx.set_gas(gas);
-    let pending = client.send_transaction(tx, None).await?;
-    let receipt = pending.confirmations(1).await?.expect("no receipt");
-    println!("Factory tx: {:?}", receipt.transaction_hash);
-
-    // Compute predicted address to show
-    let factory_addr: Address = factory;
-    let predicted = {
-        let mut hasher = sha3::Keccak256::new();
-        hasher.update([0xff]);
-        hasher.update(factory_addr.as_bytes());
-        hasher.update(salt_h.as_bytes());
-        hasher.update(bytecode_hash.as_bytes());
-        let hash = hasher.finalize();
-        Address::from_slice(&hash[12..])
-    };
-    println!("Predicted veDRAGON address: {:#x}", predicted);
+    // Connect to every chain and recompute the predicted address against
+    // that chain's factory before broadcasting anywhere.
+    let mut targets = Vec::with_capacity(args.rpc.len());
+    let mut predicted_address: Option<Address> = None;
+
+    for rpc in &args.rpc {
+        let provider = Provider::<Http>::try_from(rpc.clone())?.interval(std::time::Duration::from_millis(2000));
+        let chain_id = provider.get_chainid().await?.as_u64();
+
+        let factory = match factory_overrides.get(&chain_id) {
+            Some(addr) => addr.parse()?,
+            None => default_factory,
+        };
+        let gas_override = gas_overrides
+            .get(&chain_id)
+            .map(|g| U256::from_dec_str(g))
+            .transpose()?;
+
+        let address = compute_create2_address(&factory, &salt_h, &bytecode_hash);
+        match predicted_address {
+            None => predicted_address = Some(address),
+            Some(expected) if expected != address => {
+                eyre::bail!(
+                    "address parity broken on chain {chain_id}: expected {expected:#x}, got {address:#x} (factory {factory:#x})"
+                );
+            }
+            Some(_) => {}
+        }
+
+        let wallet = LocalWallet::from_str(pk_clean)?.with_chain_id(chain_id);
+        let client = Arc::new(SignerMiddleware::new(provider, wallet));
+
+        targets.push(ChainTarget { chain_id, rpc: rpc.clone(), factory, gas_override, client });
+    }
+
+    let predicted_address = predicted_address.expect("--rpc requires at least one URL");
+    println!("Predicted veDRAGON address (verified identical on all chains): {predicted_address:#x}");
+
+    // Deploy sequentially per chain, skipping any chain where the address
+    // is already deployed, and collect a final report table.
+    let mut report = Vec::with_capacity(targets.len());
+    for target in &targets {
+        let existing_code = target.client.get_code(predicted_address, None).await?;
+        if !existing_code.is_empty() {
+            println!("Chain {}: code already present at {predicted_address:#x}, skipping", target.chain_id);
+            report.push((target.chain_id, None, predicted_address));
+            continue;
+        }
+
+        let mut tx: TypedTransaction = TransactionRequest::new().to(target.factory).data(data.clone()).into();
+        let gas = match target.gas_override {
+            Some(gas) => gas,
+            None => target.client.estimate_gas(&tx, None).await.unwrap_or_else(|_| U256::from(3_000_000u64)),
+        };
+        tx.set_gas(gas);
+
+        println!("Chain {} ({}): broadcasting deploy tx...", target.chain_id, target.rpc);
+        let pending = target.client.send_transaction(tx, None).await?;
+        let receipt = pending.confirmations(1).await?.expect("no receipt");
+
+        let confirmed_code = target.client.get_code(predicted_address, None).await?;
+        if confirmed_code.is_empty() {
+            eyre::bail!(
+                "chain {}: deploy tx {:?} confirmed but no code found at predicted address {predicted_address:#x}",
+                target.chain_id,
+                receipt.transaction_hash
+            );
+        }
+
+        report.push((target.chain_id, Some(receipt.transaction_hash), predicted_address));
+    }
+
+    println!();
+    println!("Deployment report:");
+    println!("{:>10} | {:>66} | {:#x}", "chain_id", "tx_hash", predicted_address);
+    for (chain_id, tx_hash, address) in &report {
+        match tx_hash {
+            Some(hash) => println!("{chain_id:>10} | {hash:?} | {address:#x}"),
+            None => println!("{chain_id:>10} | {:>66} | {address:#x}", "(already deployed)"),
+        }
+    }
 
     Ok(())
 }
 
-
+fn compute_create2_address(factory: &Address, salt: &H256, bytecode_hash: &H256) -> Address {
+    let mut hasher = sha3::Keccak256::new();
+    hasher.update([0xff]);
+    hasher.update(factory.as_bytes());
+    hasher.update(salt.as_bytes());
+    hasher.update(bytecode_hash.as_bytes());
+    let hash = hasher.finalize();
+    Address::from_slice(&hash[12..])
+}
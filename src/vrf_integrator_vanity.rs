@@ -1,5 +1,5 @@
-use clap::Parser;
-// Removed unused imports
+use clap::{Parser, Subcommand};
+use create2_vanity::{to_checksum_hex, Address20, SaltStrategy, VanitySearcher};
 use hex;
 use rayon::prelude::*;
 use sha3::{Digest, Keccak256};
@@ -7,14 +7,42 @@ use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Re-derive the winning salt for a target address from its passphrase,
+    /// without storing the raw 32-byte salt anywhere.
+    Recover {
+        /// The passphrase used during the original search
+        #[arg(long)]
+        passphrase: String,
+
+        /// The vanity address to recover the salt for
+        #[arg(long)]
+        target_address: String,
+
+        /// Highest counter value to try before giving up
+        #[arg(long, default_value = "50000000")]
+        max_counter: u64,
+    },
+}
+
 #[derive(Parser, Debug)]
 #[command(author, version, about = "Generate vanity address for ChainlinkVRFIntegratorV2_5 using CREATE2 factory", long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Derive the salt from this passphrase + an incrementing counter
+    /// instead of pure randomness, so it can be regenerated from memory
+    /// with `recover` instead of copying 32 bytes of hex around.
+    #[arg(long)]
+    passphrase: Option<String>,
+
     /// Target address prefix (e.g., "69" for hex)
     #[arg(short, long, default_value = "69")]
     prefix: String,
 
-    /// Target address suffix (e.g., "a777")  
+    /// Target address suffix (e.g., "a777")
     #[arg(short, long, default_value = "a777")]
     suffix: String,
 
@@ -37,6 +65,71 @@ struct Args {
     /// Maximum attempts before giving up
     #[arg(short, long, default_value = "50000000")]
     max_attempts: u64,
+
+    /// Match --prefix/--suffix case-sensitively against the EIP-55 checksummed
+    /// address instead of lowercase hex
+    #[arg(long, default_value_t = false)]
+    checksum: bool,
+
+    /// Require this hex substring to appear anywhere in the address
+    #[arg(long)]
+    contains: Option<String>,
+
+    /// Require at least N leading zero bytes (saves calldata gas)
+    #[arg(long)]
+    min_leading_zero_bytes: Option<usize>,
+}
+
+/// A single matching predicate evaluated against a candidate CREATE2 address.
+/// `VanityMode`s compose (a candidate must satisfy all of them), built from
+/// whichever CLI flags the user opted into.
+#[derive(Debug, Clone)]
+enum VanityMode {
+    /// Legacy behavior: lowercase hex prefix/suffix match.
+    PrefixSuffix { prefix: String, suffix: String },
+    /// Case-sensitive match against the EIP-55 checksummed address.
+    Checksum { prefix: String, suffix: String },
+    /// Lowercase hex substring match anywhere in the address.
+    Contains { pattern: String },
+    /// Require at least `min` leading zero bytes.
+    MinLeadingZeroBytes { min: usize },
+}
+
+impl VanityMode {
+    fn matches(&self, address: &Address20) -> bool {
+        match self {
+            VanityMode::PrefixSuffix { prefix, suffix } => {
+                let addr_hex = hex::encode(address);
+                addr_hex.starts_with(prefix.as_str()) && addr_hex.ends_with(suffix.as_str())
+            }
+            VanityMode::Checksum { prefix, suffix } => {
+                let checksummed = to_checksum_hex(address);
+                let without_prefix = &checksummed[2..];
+                (prefix.is_empty() || without_prefix.starts_with(prefix.as_str()))
+                    && (suffix.is_empty() || without_prefix.ends_with(suffix.as_str()))
+            }
+            VanityMode::Contains { pattern } => hex::encode(address).contains(pattern.as_str()),
+            VanityMode::MinLeadingZeroBytes { min } => address.iter().take_while(|b| **b == 0).count() >= *min,
+        }
+    }
+}
+
+fn build_modes(args: &Args, prefix: &str, suffix: &str) -> Vec<VanityMode> {
+    // Checksum matching is case-sensitive against the EIP-55 string, so the
+    // raw, case-preserved pattern is used as-is; the legacy lowercase hex
+    // mode gets a lowercased copy here.
+    let mut modes = vec![if args.checksum {
+        VanityMode::Checksum { prefix: prefix.to_string(), suffix: suffix.to_string() }
+    } else {
+        VanityMode::PrefixSuffix { prefix: prefix.to_lowercase(), suffix: suffix.to_lowercase() }
+    }];
+    if let Some(pattern) = &args.contains {
+        modes.push(VanityMode::Contains { pattern: pattern.to_lowercase() });
+    }
+    if let Some(min) = args.min_leading_zero_bytes {
+        modes.push(VanityMode::MinLeadingZeroBytes { min });
+    }
+    modes
 }
 
 const CREATE2_FACTORY: &str = "0xAA28020DDA6b954D16208eccF873D79AC6533833";
@@ -44,52 +137,61 @@ const CREATE2_FACTORY: &str = "0xAA28020DDA6b954D16208eccF873D79AC6533833";
 // ChainlinkVRFIntegratorV2_5 bytecode hash (computed from actual contract)
 const VRF_INTEGRATOR_BYTECODE_HASH: &str = "0x155b7a044a741036ad9fb7dfa1f5f0194ac8e3e0aa416e1c0755c718bf2ad11c";
 
+/// Converts a raw CLI prefix/suffix into the hex pattern used for matching.
+/// All-hex input is passed through case-preserved (so `--checksum` can match
+/// uppercase nibbles); anything else is treated as ASCII and hex-encoded.
+/// Lowercasing for the non-checksum hex modes happens in `build_modes`.
 fn string_to_hex_prefix(s: &str) -> String {
     if s.chars().all(|c| c.is_ascii_hexdigit()) {
-        s.to_lowercase()
+        s.to_string()
     } else {
         // Convert string to hex
         hex::encode(s.as_bytes())
     }
 }
 
-fn compute_create2_address(factory: &str, salt: &str, bytecode_hash: &str) -> String {
-    let factory_bytes = hex::decode(&factory[2..]).expect("Invalid factory address");
-    let salt_bytes = hex::decode(&salt[2..]).expect("Invalid salt");
-    let bytecode_hash_bytes = hex::decode(&bytecode_hash[2..]).expect("Invalid bytecode hash");
-    
-    let mut hasher = Keccak256::new();
-    hasher.update(&[0xff]);
-    hasher.update(&factory_bytes);
-    hasher.update(&salt_bytes);
-    hasher.update(&bytecode_hash_bytes);
-    
-    let result = hasher.finalize();
-    let address = &result[12..]; // Take last 20 bytes
-    format!("0x{}", hex::encode(address))
+fn parse_address20(hex_str: &str) -> Address20 {
+    let bytes = hex::decode(hex_str.trim_start_matches("0x")).expect("invalid address hex");
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&bytes);
+    address
 }
 
-fn generate_random_salt() -> String {
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
-    let salt: [u8; 32] = rng.gen();
-    format!("0x{}", hex::encode(salt))
+fn parse_bytecode_hash(hex_str: &str) -> [u8; 32] {
+    let bytes = hex::decode(hex_str.trim_start_matches("0x")).expect("invalid bytecode hash hex");
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&bytes);
+    hash
 }
 
-fn check_vanity_pattern(address: &str, prefix: &str, suffix: &str) -> bool {
-    let addr_lower = address.to_lowercase();
-    let prefix_lower = prefix.to_lowercase();
-    let suffix_lower = suffix.to_lowercase();
-    
-    if !addr_lower.starts_with(&format!("0x{}", prefix_lower)) {
-        return false;
-    }
-    
-    if !addr_lower.ends_with(&suffix_lower) {
-        return false;
-    }
-    
-    true
+/// Build the constant 85-byte CREATE2 preimage prefix/suffix
+/// (`0xff || factory(20) || 0‑salt(32) || bytecode_hash(32)`) once so the
+/// passphrase search loop below only has to overwrite the 32 salt bytes
+/// per attempt. Used instead of `VanitySearcher` because passphrase-derived
+/// salts aren't one of the library's sequential/random strategies.
+fn init_create2_preimage(factory: &Address20, bytecode_hash: &[u8; 32]) -> [u8; 85] {
+    let mut preimage = [0u8; 85];
+    preimage[0] = 0xff;
+    preimage[1..21].copy_from_slice(factory);
+    preimage[53..85].copy_from_slice(bytecode_hash);
+    preimage
+}
+
+fn compute_create2_address_from_preimage(preimage: &[u8; 85]) -> Address20 {
+    let hash = Keccak256::digest(preimage);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// `salt = keccak256(passphrase_bytes || counter_be_u64)`.
+fn derive_passphrase_salt(passphrase: &str, counter: u64) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(passphrase.as_bytes());
+    hasher.update(counter.to_be_bytes());
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hasher.finalize());
+    out
 }
 
 fn _compute_vrf_integrator_bytecode_hash(registry: &str) -> String {
@@ -97,72 +199,114 @@ fn _compute_vrf_integrator_bytecode_hash(registry: &str) -> String {
     // This is a placeholder implementation
     println!("Computing bytecode hash for ChainlinkVRFIntegratorV2_5...");
     println!("Registry: {}", registry);
-    
+
     // This would need to be computed from the actual contract bytecode + constructor args
     // For now, using a placeholder
     "0x1234567890123456789012345678901234567890123456789012345678901234".to_string()
 }
 
-fn search_vanity_salt(
-    factory: &str,
-    bytecode_hash: &str,
-    prefix: &str,
-    suffix: &str,
+/// Search using a passphrase-derived salt stream: each thread walks a
+/// non-overlapping counter range, `salt = keccak256(passphrase || counter)`.
+fn search_with_passphrase(
+    factory: &Address20,
+    bytecode_hash: &[u8; 32],
+    modes: &[VanityMode],
+    passphrase: &str,
     max_attempts: u64,
     thread_count: usize,
-) -> Option<(String, String, u64, f64)> {
+) -> Option<(Address20, u64, u64, f64)> {
     let found = Arc::new(AtomicBool::new(false));
     let attempts = Arc::new(AtomicU64::new(0));
     let start_time = Instant::now();
-    
+    let per_thread = (max_attempts / thread_count as u64).max(1);
+
     println!("🔍 Searching for vanity salt...");
     println!();
-    
+
     let result = (0..thread_count)
         .into_par_iter()
-        .map(|_| {
+        .map(|thread_id| {
             let mut local_attempts = 0u64;
             let local_found = Arc::clone(&found);
             let local_attempts_counter = Arc::clone(&attempts);
-            
-            while !local_found.load(Ordering::Relaxed) && local_attempts < max_attempts / thread_count as u64 {
-                let salt = generate_random_salt();
-                let address = compute_create2_address(factory, &salt, bytecode_hash);
-                
+            let mut preimage = init_create2_preimage(factory, bytecode_hash);
+            let counter_base = thread_id as u64 * per_thread;
+
+            while !local_found.load(Ordering::Relaxed) && local_attempts < per_thread {
+                let counter = counter_base + local_attempts;
+                let salt_bytes = derive_passphrase_salt(passphrase, counter);
+                preimage[21..53].copy_from_slice(&salt_bytes);
+                let address = compute_create2_address_from_preimage(&preimage);
+
                 local_attempts += 1;
-                
+
                 if local_attempts % 10000 == 0 {
                     let total_attempts = local_attempts_counter.fetch_add(10000, Ordering::Relaxed) + 10000;
                     let elapsed = start_time.elapsed().as_secs_f64();
                     let rate = if elapsed > 0.0 { total_attempts as f64 / elapsed } else { 0.0 };
-                    
-                    print!("\r⏱️  Attempts: {} | Rate: {:.0}/s | Elapsed: {:.1}s", 
+
+                    print!("\r⏱️  Attempts: {} | Rate: {:.0}/s | Elapsed: {:.1}s",
                            total_attempts, rate, elapsed);
                     std::io::Write::flush(&mut std::io::stdout()).unwrap();
                 }
-                
-                if check_vanity_pattern(&address, prefix, suffix) {
+
+                if modes.iter().all(|m| m.matches(&address)) {
                     local_found.store(true, Ordering::Relaxed);
                     let elapsed = start_time.elapsed().as_secs_f64();
                     let total_attempts = local_attempts_counter.load(Ordering::Relaxed) + local_attempts;
-                    return Some((salt, address, total_attempts, elapsed));
+                    return Some((address, counter, total_attempts, elapsed));
                 }
             }
-            
+
             local_attempts_counter.fetch_add(local_attempts, Ordering::Relaxed);
             None
         })
         .find_any(|x| x.is_some());
-    
-    match result {
-        Some(Some(result)) => Some(result),
-        _ => None,
-    }
+
+    result.flatten()
+}
+
+/// Re-walk counters from a passphrase until the derived salt reproduces
+/// `target_address`, so a salt can be regenerated on another machine from
+/// memory instead of storing 32 bytes of hex.
+fn recover_salt_from_passphrase(
+    searcher: &VanitySearcher,
+    passphrase: &str,
+    target_address: &Address20,
+    max_counter: u64,
+) -> Option<([u8; 32], u64)> {
+    (0..max_counter).into_par_iter().find_map_any(|counter| {
+        let salt = derive_passphrase_salt(passphrase, counter);
+        if &searcher.address_for_salt(&salt) == target_address {
+            Some((salt, counter))
+        } else {
+            None
+        }
+    })
 }
 
 fn main() {
     let args = Args::parse();
-    
+    let factory = parse_address20(CREATE2_FACTORY);
+    let bytecode_hash = parse_bytecode_hash(VRF_INTEGRATOR_BYTECODE_HASH);
+    let searcher = VanitySearcher::new(factory, bytecode_hash, args.threads);
+
+    if let Some(Command::Recover { passphrase, target_address, max_counter }) = &args.command {
+        println!("🔑 Recovering salt for {} from passphrase...", target_address);
+        let target = parse_address20(target_address);
+        match recover_salt_from_passphrase(&searcher, passphrase, &target, *max_counter) {
+            Some((salt, counter)) => {
+                println!("✅ Recovered!");
+                println!("🧂 Salt: 0x{}", hex::encode(salt));
+                println!("🔢 Counter: {}", counter);
+            }
+            None => {
+                println!("❌ No counter up to {} reproduced {}", max_counter, target_address);
+            }
+        }
+        return;
+    }
+
     println!("🚀 OMNIDRAGON VRF INTEGRATOR V2.5 VANITY GENERATOR");
     println!("════════════════════════════════════════════════");
     println!("🎯 Target Pattern: 0x{}...{}", args.prefix, args.suffix);
@@ -173,43 +317,67 @@ fn main() {
     println!("🧵 Threads: {}", args.threads);
     println!("🔢 Max Attempts: {}", args.max_attempts);
     println!();
-    
-    // Use the computed bytecode hash for ChainlinkVRFIntegratorV2_5
-    let bytecode_hash = VRF_INTEGRATOR_BYTECODE_HASH;
-    
-    println!("📝 Bytecode Hash: {}", bytecode_hash);
-    
+
+    println!("📝 Bytecode Hash: {}", VRF_INTEGRATOR_BYTECODE_HASH);
+
     let hex_prefix = string_to_hex_prefix(&args.prefix);
     let hex_suffix = string_to_hex_prefix(&args.suffix);
-    
-    match search_vanity_salt(
-        CREATE2_FACTORY,
-        &bytecode_hash,
-        &hex_prefix,
-        &hex_suffix,
-        args.max_attempts,
-        args.threads,
-    ) {
-        Some((salt, address, attempts, elapsed)) => {
+    let modes = build_modes(&args, &hex_prefix, &hex_suffix);
+
+    // Passphrase runs recover via (passphrase, counter) instead of storing a
+    // salt (see `recover_salt_from_passphrase`); plain random runs have no
+    // passphrase to recover from, so the salt itself has to be reported.
+    let found = match &args.passphrase {
+        Some(passphrase) => {
+            search_with_passphrase(&factory, &bytecode_hash, &modes, passphrase, args.max_attempts, args.threads)
+                .map(|(address, counter, attempts, elapsed)| (address, None, Some(counter), attempts, elapsed))
+        }
+        None => {
+            let modes = modes.clone();
+            let matcher = move |address: &Address20| modes.iter().all(|m| m.matches(address));
+            searcher
+                .search(SaltStrategy::Random, args.max_attempts, &matcher, 100_000, None, |total| {
+                    print!("\r⏱️  Attempts: {}", total);
+                    std::io::Write::flush(&mut std::io::stdout()).unwrap();
+                })
+                .map(|r| (r.address, Some(r.salt), None, r.attempts, 0.0))
+        }
+    };
+
+    match found {
+        Some((address, salt, counter, attempts, elapsed)) => {
+            let address_hex = format!("0x{}", hex::encode(address));
             println!();
             println!("🎉 VANITY ADDRESS FOUND!");
             println!("════════════════════════");
-            println!("✨ Address: {}", address);
-            println!("🧂 Salt: {}", salt);
-            println!("⏱️  Time: {:.2}s", elapsed);
+            println!("✨ Address: {}", address_hex);
+            match (&args.passphrase, counter) {
+                (Some(passphrase), Some(counter)) => {
+                    println!("🔑 Passphrase: {}", passphrase);
+                    println!("🔢 Counter: {}", counter);
+                }
+                _ => {}
+            }
             println!("🔢 Attempts: {}", attempts);
-            println!("📈 Rate: {:.0} attempts/sec", attempts as f64 / elapsed);
+            if elapsed > 0.0 {
+                println!("⏱️  Time: {:.2}s", elapsed);
+                println!("📈 Rate: {:.0} attempts/sec", attempts as f64 / elapsed);
+            }
             println!();
-            
+
             println!("📋 DEPLOYMENT COMMANDS:");
             println!("═══════════════════════");
             println!("// Add to your .env file:");
-            println!("VANITY_VRF_INTEGRATOR_SALT={}", salt);
-            println!("EXPECTED_VRF_INTEGRATOR_ADDRESS={}", address);
+            if let Some(salt) = salt {
+                println!("VANITY_VRF_INTEGRATOR_SALT=0x{}", hex::encode(salt));
+            }
+            println!("EXPECTED_VRF_INTEGRATOR_ADDRESS={}", address_hex);
             println!();
             println!("// Use in Solidity script:");
-            println!("bytes32 constant VRF_INTEGRATOR_VANITY_SALT = {};", salt);
-            println!("address constant EXPECTED_VRF_INTEGRATOR_ADDRESS = {};", address);
+            if let Some(salt) = salt {
+                println!("bytes32 constant VRF_INTEGRATOR_VANITY_SALT = 0x{};", hex::encode(salt));
+            }
+            println!("address constant EXPECTED_VRF_INTEGRATOR_ADDRESS = {};", address_hex);
             println!();
             println!("🚀 Ready to deploy ChainlinkVRFIntegratorV2_5 with vanity address!");
         }
@@ -222,7 +390,7 @@ fn main() {
             println!("  - More threads (--threads)");
         }
     }
-    
+
     println!();
     println!("💡 Tips for better results:");
     println!("  - Use shorter patterns (2-4 chars)");
@@ -232,4 +400,4 @@ fn main() {
     println!("🔗 Next steps:");
     println!("  1. Update script/DeployVanityVRFIntegrator.s.sol with generated salt");
     println!("  2. Run: forge script script/DeployVanityVRFIntegrator.s.sol --rpc-url $RPC_URL_SONIC --broadcast");
-}
\ No newline at end of file
+}
@@ -1,12 +1,47 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use create2_vanity::{to_checksum_hex, VanitySearcher};
+use ethers::abi::{encode, Token};
 use ethers::types::{Address, H256, U256};
+use ethers::utils::get_contract_address;
 use hex;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
 use rayon::prelude::*;
+use regex::RegexSetBuilder;
 use sha3::{Digest, Keccak256};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
+/// Address derivation scheme to search over.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Scheme {
+    /// `keccak256(0xff ++ factory ++ salt ++ bytecode_hash)[12..]`, deployed
+    /// through the CREATE2 factory.
+    Create2,
+    /// `keccak256(rlp([deployer, nonce]))[12..]`, deployed directly from an
+    /// EOA/factory without a CREATE2 factory; iterates the deployer's nonce
+    /// instead of hashing salts.
+    Create,
+}
+
+/// What a caller needs to reproduce a found address: a CREATE2 salt, or the
+/// deployer nonce for a plain CREATE deploy.
+#[derive(Debug, Clone, Copy)]
+enum Identifier {
+    Salt(H256),
+    Nonce(u64),
+}
+
+impl std::fmt::Display for Identifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Identifier::Salt(salt) => write!(f, "salt 0x{}", hex::encode(salt.as_bytes())),
+            Identifier::Nonce(nonce) => write!(f, "nonce {}", nonce),
+        }
+    }
+}
+
 #[derive(Parser, Debug)]
 #[command(author, version, about = "Generate vanity address for OmniDragonVRFConsumerV2_5 using CREATE2 factory", long_about = None)]
 struct Args {
@@ -45,6 +80,40 @@ struct Args {
     /// Maximum attempts before giving up
     #[arg(short, long, default_value = "50000000")]
     max_attempts: u64,
+
+    /// Contract creation code: path to a hex file, or inline 0x-prefixed hex
+    #[arg(long, default_value = "vrf_consumer_initcode.hex")]
+    init_code: String,
+
+    /// Match the address against one or more regex patterns (OR'd together)
+    /// instead of --prefix/--suffix. Case-insensitive unless --case-sensitive is set.
+    #[arg(long)]
+    regex: Vec<String>,
+
+    /// Match --regex patterns against the EIP-55 checksummed address instead
+    /// of the all-lowercase hex
+    #[arg(long, default_value_t = false)]
+    case_sensitive: bool,
+
+    /// 256-bit hex seed for the per-thread salt streams. Defaults to an
+    /// OS-random value, which is printed so the run can be reproduced.
+    #[arg(long)]
+    seed: Option<String>,
+
+    /// Score candidates by leading zero bytes instead of --prefix/--suffix,
+    /// stopping once an address with at least N leading zero bytes is found
+    #[arg(long)]
+    zeros: Option<usize>,
+
+    /// Like --zeros but with no target: keep searching until --max-attempts,
+    /// printing each new best-scoring address as it's found
+    #[arg(long, default_value_t = false)]
+    maximize_zeros: bool,
+
+    /// Address derivation scheme: "create2" (default, needs a CREATE2
+    /// factory + salt) or "create" (nonce-based, for direct EOA deploys)
+    #[arg(long, value_enum, default_value = "create2")]
+    scheme: Scheme,
 }
 
 const CREATE2_FACTORY: &str = "0xAA28020DDA6b954D16208eccF873D79AC6533833";
@@ -55,64 +124,143 @@ fn string_to_hex_pattern(input: &str) -> String {
     hex::encode(bytes)
 }
 
+/// Load the contract creation code from `--init-code`: a path to a hex file
+/// if one exists at that path, otherwise the argument is treated as inline
+/// (optionally 0x-prefixed) hex.
+///
+/// An argument that isn't valid inline hex (e.g. the default
+/// `vrf_consumer_initcode.hex`) is necessarily meant as a file path, so a
+/// missing file is a hard error rather than falling through to hashing the
+/// path string itself.
+fn load_init_code(init_code_arg: &str) -> Vec<u8> {
+    let path = std::path::Path::new(init_code_arg);
+    if path.is_file() {
+        let hex_str = std::fs::read_to_string(path).expect("failed to read --init-code file");
+        return parse_init_code_hex(&hex_str);
+    }
+
+    let looks_like_hex = {
+        let trimmed = init_code_arg.trim().trim_start_matches("0x");
+        !trimmed.is_empty() && trimmed.chars().all(|c| c.is_ascii_hexdigit())
+    };
+    if !looks_like_hex {
+        panic!(
+            "--init-code {:?} is not a readable file and not valid inline hex; pass a path to a hex file or 0x-prefixed hex bytes",
+            init_code_arg
+        );
+    }
+    parse_init_code_hex(init_code_arg)
+}
+
+/// Parses `hex_str` (optionally `0x`-prefixed, with surrounding whitespace)
+/// into bytes, rejecting anything containing non-hex characters instead of
+/// silently discarding them.
+fn parse_init_code_hex(hex_str: &str) -> Vec<u8> {
+    let mut clean = hex_str.trim().trim_start_matches("0x").to_string();
+    assert!(
+        !clean.is_empty() && clean.chars().all(|c| c.is_ascii_hexdigit()),
+        "--init-code contains non-hex characters: {:?}",
+        hex_str.trim()
+    );
+    if clean.len() % 2 == 1 {
+        clean = format!("0{}", clean);
+    }
+    hex::decode(&clean).expect("invalid --init-code hex")
+}
+
+/// Append the ABI-encoded constructor tuple
+/// `(address endpoint, address deployer, address coordinator, uint256 subscriptionId, bytes32 keyHash)`
+/// to the real contract creation code, so `keccak256(...)` of the result is
+/// the actual CREATE2 bytecode hash rather than a placeholder.
 fn create_constructor_bytecode(
+    init_code: &[u8],
     endpoint: &str,
     deployer: &str,
     coordinator: &str,
     subscription_id: &str,
     key_hash: &str,
 ) -> Vec<u8> {
-    // This is a simplified version - in reality you'd need the actual contract bytecode
-    // For now, we'll use a placeholder that represents the constructor parameters
-    let mut bytecode = Vec::new();
-    
-    // Contract creation code would go here
-    // For this example, we'll create a mock bytecode hash
-    let constructor_params = format!(
-        "{}{}{}{}{}",
-        endpoint, deployer, coordinator, subscription_id, key_hash
-    );
-    
-    let mut hasher = Keccak256::new();
-    hasher.update(constructor_params.as_bytes());
-    bytecode.extend_from_slice(&hasher.finalize());
-    
-    bytecode
-}
+    let endpoint_addr: Address = endpoint.parse().expect("invalid endpoint address");
+    let deployer_addr: Address = deployer.parse().expect("invalid deployer address");
+    let coordinator_addr: Address = coordinator.parse().expect("invalid coordinator address");
+    let subscription_id_u256 = U256::from_dec_str(subscription_id).expect("invalid subscription id");
+    let key_hash_bytes: H256 = key_hash.parse().expect("invalid key hash");
 
-fn calculate_create2_address(factory: &Address, salt: &H256, bytecode_hash: &H256) -> Address {
-    let mut hasher = Keccak256::new();
-    hasher.update(&[0xff]);
-    hasher.update(factory.as_bytes());
-    hasher.update(salt.as_bytes());
-    hasher.update(bytecode_hash.as_bytes());
-    
-    let hash = hasher.finalize();
-    let mut addr_bytes = [0u8; 20];
-    addr_bytes.copy_from_slice(&hash[12..32]);
-    Address::from(addr_bytes)
+    let encoded_args = encode(&[
+        Token::Address(endpoint_addr),
+        Token::Address(deployer_addr),
+        Token::Address(coordinator_addr),
+        Token::Uint(subscription_id_u256),
+        Token::FixedBytes(key_hash_bytes.as_bytes().to_vec()),
+    ]);
+
+    [init_code, &encoded_args].concat()
 }
 
 fn matches_pattern(address: &Address, prefix: &str, suffix: &str) -> bool {
     let addr_str = format!("{:x}", address);
-    
+
     // Check if address starts with prefix (after 0x)
     let starts_match = if prefix.is_empty() {
         true
     } else {
         addr_str.starts_with(&prefix.to_lowercase())
     };
-    
+
     // Check if address ends with suffix
     let ends_match = if suffix.is_empty() {
         true
     } else {
         addr_str.ends_with(&suffix.to_lowercase())
     };
-    
+
     starts_match && ends_match
 }
 
+/// Test an address against a compiled set of `--regex` alternatives,
+/// matching the checksummed form when `case_sensitive` is set and the
+/// plain lowercase hex otherwise.
+fn matches_regex(address: &Address, regex_set: &regex::RegexSet, case_sensitive: bool) -> bool {
+    let addr_str = if case_sensitive {
+        to_checksum_hex(address.as_fixed_bytes())[2..].to_string()
+    } else {
+        format!("{:x}", address)
+    };
+    regex_set.is_match(&addr_str)
+}
+
+/// Derive a per-thread RNG seed by XORing the thread index into the low 8
+/// bytes of the run's `--seed`, so every thread draws an independent base
+/// salt from the same reproducible root seed.
+fn thread_seed(seed: &H256, thread_index: usize) -> [u8; 32] {
+    let mut bytes = *seed.as_fixed_bytes();
+    let idx_bytes = (thread_index as u64).to_be_bytes();
+    for (b, idx_b) in bytes[24..].iter_mut().zip(idx_bytes.iter()) {
+        *b ^= idx_b;
+    }
+    bytes
+}
+
+/// Count leading zero bytes of the 20-byte address directly (not the hex
+/// string), used to score candidates for --zeros/--maximize-zeros.
+fn leading_zero_bytes(address: &Address) -> usize {
+    address.as_bytes().iter().take_while(|b| **b == 0).count()
+}
+
+/// Increment a 32-byte salt as a big-endian 256-bit integer, so a thread's
+/// salt stream walks sequential values from its random base instead of
+/// redrawing (and potentially colliding with) random salts every attempt.
+fn increment_salt(salt: &mut [u8; 32]) {
+    for byte in salt.iter_mut().rev() {
+        if *byte == 0xff {
+            *byte = 0;
+        } else {
+            *byte += 1;
+            break;
+        }
+    }
+}
+
 fn main() {
     let args = Args::parse();
     
@@ -140,88 +288,207 @@ fn main() {
     // Parse addresses
     let factory_addr: Address = CREATE2_FACTORY.parse().expect("Invalid factory address");
     let deployer_addr: Address = args.deployer.parse().expect("Invalid deployer address");
-    
-    // Create bytecode with constructor parameters
-    let bytecode = create_constructor_bytecode(
-        &args.endpoint,
-        &args.deployer,
-        &args.coordinator,
-        &args.subscription_id,
-        &args.key_hash,
-    );
-    
-    let bytecode_hash = H256::from_slice(&Keccak256::digest(&bytecode));
-    
-    println!("📝 Bytecode Hash: 0x{}", hex::encode(bytecode_hash.as_bytes()));
-    println!("🔍 Searching for vanity salt...");
+
+    // The CREATE scheme derives addresses from keccak256(rlp([deployer,
+    // nonce])) and never touches the init code / bytecode hash, so skip
+    // loading/hashing it there — otherwise a default --init-code file that
+    // doesn't exist would panic on startup even for a --scheme create run.
+    let create2_searcher = if args.scheme == Scheme::Create2 {
+        // Create bytecode with constructor parameters
+        let init_code = load_init_code(&args.init_code);
+        let bytecode = create_constructor_bytecode(
+            &init_code,
+            &args.endpoint,
+            &args.deployer,
+            &args.coordinator,
+            &args.subscription_id,
+            &args.key_hash,
+        );
+
+        let bytecode_hash = H256::from_slice(&Keccak256::digest(&bytecode));
+        println!("📝 Bytecode Hash: 0x{}", hex::encode(bytecode_hash.as_bytes()));
+
+        // Shared CREATE2 address computation (see create2-vanity crate
+        // docs); only `address_for_salt` is used here since this binary
+        // drives its own search loop (CREATE2 salt streams or CREATE
+        // nonces, regex/zeros modes) rather than `VanitySearcher::search`.
+        Some(VanitySearcher::new(*factory_addr.as_fixed_bytes(), *bytecode_hash.as_fixed_bytes(), args.threads))
+    } else {
+        None
+    };
+
+    let regex_set = if args.regex.is_empty() {
+        None
+    } else {
+        println!("🔎 Regex patterns: {:?} (case sensitive: {})", args.regex, args.case_sensitive);
+        Some(
+            RegexSetBuilder::new(&args.regex)
+                .case_insensitive(!args.case_sensitive)
+                .build()
+                .expect("invalid --regex pattern"),
+        )
+    };
+
+    // Reproducible, non-overlapping salt streams: each thread XORs its
+    // index into the root seed, draws one random 256-bit base salt, then
+    // increments that salt as a big-endian integer per attempt instead of
+    // redrawing (and risking cross-thread collisions on) fresh randomness.
+    let seed: H256 = match &args.seed {
+        Some(s) => s.parse().expect("invalid --seed, expected 32-byte hex"),
+        None => H256::from(rand::random::<[u8; 32]>()),
+    };
+    println!("🔗 Scheme: {:?}", args.scheme);
+    if args.scheme == Scheme::Create2 {
+        println!("🌱 Seed: 0x{}", hex::encode(seed.as_bytes()));
+    }
+    println!("🔍 Searching for vanity address...");
     println!();
 
+    let zeros_mode = args.zeros.is_some() || args.maximize_zeros;
+
     let found = Arc::new(AtomicBool::new(false));
     let attempts = Arc::new(AtomicU64::new(0));
+    let best_score = Arc::new(AtomicUsize::new(0));
+    let best = Arc::new(Mutex::new(None::<(Identifier, Address, usize)>));
     let start_time = Instant::now();
+    let per_thread = (args.max_attempts / args.threads as u64).max(1);
 
-    // Parallel search
-    let result = (0..args.max_attempts)
-        .into_par_iter()
-        .map(|i| {
-            if found.load(Ordering::Relaxed) {
-                return None;
-            }
+    if zeros_mode {
+        match args.zeros {
+            Some(target) => println!("🎯 Scoring mode: stop at {} leading zero bytes", target),
+            None => println!("🎯 Scoring mode: maximize leading zero bytes over {} attempts", args.max_attempts),
+        }
+    }
 
-            // Generate random salt
-            let salt_bytes: [u8; 32] = rand::random();
-            let salt = H256::from(salt_bytes);
-            
-            // Calculate CREATE2 address
-            let address = calculate_create2_address(&factory_addr, &salt, &bytecode_hash);
-            
-            // Check if it matches our pattern
-            if matches_pattern(&address, &hex_prefix, &args.suffix) {
+    // Shared between both schemes: scores/matches a candidate address and,
+    // on a progress tick, reports attempt rate. Returns Some(..) once the
+    // search for this thread should stop (a match, or a --zeros target hit).
+    let evaluate = |address: Address, identifier: Identifier| -> Option<(Identifier, Address)> {
+        if zeros_mode {
+            let score = leading_zero_bytes(&address);
+            if score > best_score.fetch_max(score, Ordering::Relaxed) {
+                *best.lock().unwrap() = Some((identifier, address, score));
+                println!("🏅 New record: 0x{:x} ({}) — {} leading zero bytes", address, identifier, score);
+            }
+            if args.zeros.map_or(false, |target| score >= target) {
                 found.store(true, Ordering::Relaxed);
-                return Some((salt, address));
+                return Some((identifier, address));
             }
-            
-            let current_attempts = attempts.fetch_add(1, Ordering::Relaxed);
-            if current_attempts % 100000 == 0 {
-                let elapsed = start_time.elapsed();
-                let rate = current_attempts as f64 / elapsed.as_secs_f64();
-                println!("⏱️  Attempts: {} | Rate: {:.0}/s | Elapsed: {:.1}s", 
-                    current_attempts, rate, elapsed.as_secs_f64());
+        } else {
+            let is_match = match &regex_set {
+                Some(set) => matches_regex(&address, set, args.case_sensitive),
+                None => matches_pattern(&address, &hex_prefix, &args.suffix),
+            };
+            if is_match {
+                found.store(true, Ordering::Relaxed);
+                return Some((identifier, address));
+            }
+        }
+
+        let current_attempts = attempts.fetch_add(1, Ordering::Relaxed);
+        if current_attempts % 100000 == 0 {
+            let elapsed = start_time.elapsed();
+            let rate = current_attempts as f64 / elapsed.as_secs_f64();
+            println!("⏱️  Attempts: {} | Rate: {:.0}/s | Elapsed: {:.1}s", current_attempts, rate, elapsed.as_secs_f64());
+        }
+        None
+    };
+
+    // Parallel search: CREATE2 walks a per-thread salt stream, CREATE walks
+    // a per-thread slice of the deployer's nonce space. Both funnel every
+    // candidate through the shared `evaluate` above.
+    let result = (0..args.threads)
+        .into_par_iter()
+        .map(|thread_id| match args.scheme {
+            Scheme::Create2 => {
+                let create2_searcher = create2_searcher.as_ref().expect("bytecode hash not loaded for --scheme create2");
+                let mut rng = StdRng::from_seed(thread_seed(&seed, thread_id));
+                let mut salt_bytes = [0u8; 32];
+                rng.fill_bytes(&mut salt_bytes);
+
+                for _ in 0..per_thread {
+                    if found.load(Ordering::Relaxed) {
+                        return None;
+                    }
+
+                    let salt = H256::from(salt_bytes);
+                    let address = Address::from(create2_searcher.address_for_salt(&salt_bytes));
+                    if let Some(result) = evaluate(address, Identifier::Salt(salt)) {
+                        return Some(result);
+                    }
+
+                    increment_salt(&mut salt_bytes);
+                }
+                None
+            }
+            Scheme::Create => {
+                let nonce_start = thread_id as u64 * per_thread;
+                for i in 0..per_thread {
+                    if found.load(Ordering::Relaxed) {
+                        return None;
+                    }
+
+                    let nonce = nonce_start + i;
+                    let address = get_contract_address(deployer_addr, U256::from(nonce));
+                    if let Some(result) = evaluate(address, Identifier::Nonce(nonce)) {
+                        return Some(result);
+                    }
+                }
+                None
             }
-            
-            None
         })
         .find_any(|x| x.is_some());
 
+    // In scoring mode an exact target might never be hit before
+    // max_attempts runs out; fall back to whatever `best` has accumulated.
+    let result = result.flatten().or_else(|| {
+        if zeros_mode {
+            best.lock().unwrap().map(|(identifier, address, _)| (identifier, address))
+        } else {
+            None
+        }
+    });
+
     match result {
-        Some(Some((salt, address))) => {
+        Some((identifier, address)) => {
             let elapsed = start_time.elapsed();
             let total_attempts = attempts.load(Ordering::Relaxed);
-            
+
             println!("🎉 VANITY ADDRESS FOUND!");
             println!("════════════════════════");
             println!("✨ Address: 0x{:x}", address);
-            println!("🧂 Salt: 0x{}", hex::encode(salt.as_bytes()));
+            println!("🔑 {}", identifier);
+            if zeros_mode {
+                println!("0️⃣ Leading zero bytes: {}", leading_zero_bytes(&address));
+            }
             println!("⏱️  Time: {:.2}s", elapsed.as_secs_f64());
             println!("🔢 Attempts: {}", total_attempts);
             println!("📈 Rate: {:.0} attempts/sec", total_attempts as f64 / elapsed.as_secs_f64());
             println!();
-            
+
             println!("📋 DEPLOYMENT COMMANDS:");
             println!("═══════════════════════");
-            println!("// Add to your .env file:");
-            println!("VANITY_VRF_SALT=0x{}", hex::encode(salt.as_bytes()));
-            println!("EXPECTED_VRF_ADDRESS=0x{:x}", address);
-            println!();
-            
-            println!("// Use in Solidity script:");
-            println!("bytes32 constant VRF_VANITY_SALT = 0x{};", hex::encode(salt.as_bytes()));
-            println!("address constant EXPECTED_VRF_ADDRESS = 0x{:x};", address);
+            match identifier {
+                Identifier::Salt(salt) => {
+                    println!("// Add to your .env file:");
+                    println!("VANITY_VRF_SALT=0x{}", hex::encode(salt.as_bytes()));
+                    println!("EXPECTED_VRF_ADDRESS=0x{:x}", address);
+                    println!();
+                    println!("// Use in Solidity script:");
+                    println!("bytes32 constant VRF_VANITY_SALT = 0x{};", hex::encode(salt.as_bytes()));
+                    println!("address constant EXPECTED_VRF_ADDRESS = 0x{:x};", address);
+                }
+                Identifier::Nonce(nonce) => {
+                    println!("// Deploy directly from the deployer EOA at nonce {}:", nonce);
+                    println!("EXPECTED_VRF_ADDRESS=0x{:x}", address);
+                    println!("REQUIRED_DEPLOYER_NONCE={}", nonce);
+                }
+            }
             println!();
-            
+
             println!("🚀 Ready to deploy OmniDragonVRFConsumerV2_5 with vanity address!");
         }
-        _ => {
+        None => {
             println!("❌ No vanity address found after {} attempts", args.max_attempts);
             println!("💡 Try:");
             println!("   - Shorter/simpler pattern");
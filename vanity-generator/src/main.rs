@@ -1,23 +1,55 @@
 use clap::{Arg, Command};
+use create2_vanity::{to_checksum_hex, Address20, Checkpoint, SaltStrategy, VanitySearcher};
 use ethers::types::{Address, H256};
 use hex;
 use indicatif::{ProgressBar, ProgressStyle};
-use rayon::prelude::*;
-use sha3::{Digest, Keccak256};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
-use std::time::Instant;
 use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// A single matching predicate evaluated against a candidate CREATE2 address.
+///
+/// `main` builds a list of these and a candidate must satisfy all of
+/// them, so modes compose instead of being mutually exclusive (e.g. you can
+/// require a checksummed pattern *and* a minimum number of leading zero
+/// bytes in the same search).
+#[derive(Debug, Clone)]
+enum VanityMode {
+    /// Legacy behavior: lowercase hex prefix/suffix match.
+    PrefixSuffix { starts_with: String, ends_with: String },
+    /// Case-sensitive match against the EIP-55 checksummed address.
+    Checksum { starts_with: String, ends_with: String },
+    /// Lowercase hex substring match anywhere in the address.
+    Contains { pattern: String },
+    /// Require at least `min` leading zero bytes (gas-optimized addresses).
+    MinLeadingZeroBytes { min: usize },
+}
+
+impl VanityMode {
+    fn matches(&self, address: &Address20) -> bool {
+        match self {
+            VanityMode::PrefixSuffix { starts_with, ends_with } => {
+                check_vanity_pattern(address, starts_with, ends_with)
+            }
+            VanityMode::Checksum { starts_with, ends_with } => {
+                let checksummed = to_checksum_hex(address);
+                let without_prefix = &checksummed[2..];
+                (starts_with.is_empty() || without_prefix.starts_with(starts_with.as_str()))
+                    && (ends_with.is_empty() || without_prefix.ends_with(ends_with.as_str()))
+            }
+            VanityMode::Contains { pattern } => hex::encode(address).to_lowercase().contains(pattern.as_str()),
+            VanityMode::MinLeadingZeroBytes { min } => address.iter().take_while(|b| **b == 0).count() >= *min,
+        }
+    }
+}
+
+fn check_vanity_pattern(address: &Address20, starts_with: &str, ends_with: &str) -> bool {
+    let addr_hex = hex::encode(address).to_lowercase();
 
-const BATCH_SIZE: u64 = 1_000_000;
+    let starts_match = if starts_with.is_empty() { true } else { addr_hex.starts_with(starts_with) };
+    let ends_match = if ends_with.is_empty() { true } else { addr_hex.ends_with(ends_with) };
 
-#[derive(Debug)]
-struct VanityConfig {
-    factory: Address,
-    bytecode_hash: H256,
-    starts_with: String,
-    ends_with: String,
-    threads: usize,
+    starts_match && ends_match
 }
 
 fn main() {
@@ -56,6 +88,27 @@ fn main() {
                 .default_value("7777")
                 .required(false),
         )
+        .arg(
+            Arg::new("checksum")
+                .long("checksum")
+                .help("Match --starts-with/--ends-with case-sensitively against the EIP-55 checksummed address instead of lowercase hex")
+                .action(clap::ArgAction::SetTrue)
+                .required(false),
+        )
+        .arg(
+            Arg::new("contains")
+                .long("contains")
+                .value_name("HEX")
+                .help("Require this hex substring to appear anywhere in the address")
+                .required(false),
+        )
+        .arg(
+            Arg::new("min-leading-zero-bytes")
+                .long("min-leading-zero-bytes")
+                .value_name("N")
+                .help("Require at least N leading zero bytes (saves calldata gas)")
+                .required(false),
+        )
         .arg(
             Arg::new("threads")
                 .long("threads")
@@ -64,44 +117,92 @@ fn main() {
                 .default_value("0")
                 .required(false),
         )
+        .arg(
+            Arg::new("resume")
+                .long("resume")
+                .help(
+                    "Resume a sequential search from its last checkpoint instead of starting at 0. \
+                     Only meaningful with the same --threads value used for the original run.",
+                )
+                .action(clap::ArgAction::SetTrue)
+                .required(false),
+        )
+        .arg(
+            Arg::new("checkpoint-file")
+                .long("checkpoint-file")
+                .value_name("PATH")
+                .help("Where to persist search progress for --resume")
+                .default_value("vanity-search.checkpoint")
+                .required(false),
+        )
         .get_matches();
 
     // Parse arguments
     let factory_str = matches.get_one::<String>("factory").unwrap();
     let bytecode_hash_str = matches.get_one::<String>("bytecode-hash").unwrap();
-    let starts_with = matches.get_one::<String>("starts-with").unwrap().to_lowercase();
-    let ends_with = matches.get_one::<String>("ends-with").unwrap().to_lowercase();
+    let starts_with_raw = matches.get_one::<String>("starts-with").unwrap().clone();
+    let ends_with_raw = matches.get_one::<String>("ends-with").unwrap().clone();
+    let checksum = matches.get_flag("checksum");
+    // Checksum matching is case-sensitive against the EIP-55 string, so the
+    // pattern must keep whatever case the user typed; only the lowercase
+    // hex modes get normalized.
+    let starts_with = if checksum { starts_with_raw.clone() } else { starts_with_raw.to_lowercase() };
+    let ends_with = if checksum { ends_with_raw.clone() } else { ends_with_raw.to_lowercase() };
+    let contains = matches.get_one::<String>("contains").map(|s| s.to_lowercase());
+    let min_leading_zero_bytes: Option<usize> = matches
+        .get_one::<String>("min-leading-zero-bytes")
+        .map(|s| s.parse().expect("--min-leading-zero-bytes must be a number"));
     let threads: usize = matches.get_one::<String>("threads").unwrap().parse().unwrap_or(0);
+    let threads = if threads == 0 { num_cpus::get() } else { threads };
+    let resume = matches.get_flag("resume");
+    let checkpoint_path = PathBuf::from(matches.get_one::<String>("checkpoint-file").unwrap());
 
     // Parse addresses and hashes
     let factory: Address = factory_str.parse().expect("Invalid factory address");
     let bytecode_hash: H256 = bytecode_hash_str.parse().expect("Invalid bytecode hash");
 
-    let config = VanityConfig {
-        factory,
-        bytecode_hash,
-        starts_with,
-        ends_with,
-        threads: if threads == 0 { num_cpus::get() } else { threads },
-    };
+    // Build the composable matcher set: the base prefix/suffix predicate
+    // (lowercase by default, checksummed if --checksum is set) plus any
+    // extra modes the user opted into.
+    let mut modes = vec![if checksum {
+        VanityMode::Checksum { starts_with: starts_with.clone(), ends_with: ends_with.clone() }
+    } else {
+        VanityMode::PrefixSuffix { starts_with: starts_with.clone(), ends_with: ends_with.clone() }
+    }];
+    if let Some(pattern) = contains {
+        modes.push(VanityMode::Contains { pattern });
+    }
+    if let Some(min) = min_leading_zero_bytes {
+        modes.push(VanityMode::MinLeadingZeroBytes { min });
+    }
 
     println!("🐉 Dragon Vanity Address Generator");
     println!("==================================");
-    println!("Factory: {:#x}", config.factory);
-    println!("Bytecode Hash: {:#x}", config.bytecode_hash);
-    println!("Pattern: 0x{}...{}", config.starts_with, config.ends_with);
-    println!("Threads: {}", config.threads);
+    println!("Factory: {:#x}", factory);
+    println!("Bytecode Hash: {:#x}", bytecode_hash);
+    println!("Pattern: 0x{}...{}", starts_with, ends_with);
+    println!("Checksum mode: {}", checksum);
+    println!("Matchers: {}", modes.len());
+    println!("Threads: {}", threads);
     println!();
 
-    // Set up thread pool
-    rayon::ThreadPoolBuilder::new()
-        .num_threads(config.threads)
-        .build_global()
-        .unwrap();
+    let start = if resume {
+        match Checkpoint::load(&checkpoint_path).and_then(|c| c.resume_start().map(|s| (s, c.attempts))) {
+            Some((resume_salt, attempts_so_far)) => {
+                println!("Resuming from checkpoint: salt {} ({} attempts so far)", resume_salt, attempts_so_far);
+                resume_salt
+            }
+            None => {
+                println!("No usable checkpoint found at {:?}, starting from 0", checkpoint_path);
+                0
+            }
+        }
+    } else {
+        0
+    };
 
+    let searcher = VanitySearcher::new(*factory.as_fixed_bytes(), *bytecode_hash.as_fixed_bytes(), threads);
     let start_time = Instant::now();
-    let found = Arc::new(AtomicBool::new(false));
-    let attempts = Arc::new(AtomicU64::new(0));
 
     // Progress bar
     let pb = ProgressBar::new_spinner();
@@ -111,27 +212,35 @@ fn main() {
             .unwrap(),
     );
 
-    // Start search with limited range that we can extend if needed
-    let max_batches = 100_000; // This gives us 100 billion attempts total
-    let result = (0u64..max_batches)
-        .into_par_iter()
-        .map(|batch| {
-            let batch_start = batch * BATCH_SIZE;
-            search_batch(&config, batch_start, BATCH_SIZE, &found, &attempts)
-        })
-        .find_any(|result| result.is_some());
-
-    if let Some(Some((salt, address))) = result {
+    // Matches the previous max_batches = 100_000 cap (BATCH_SIZE 1_000_000
+    // each), i.e. 100 billion attempts total before giving up.
+    let max_attempts = 100_000_000_000;
+    let matcher = move |address: &Address20| modes.iter().all(|m| m.matches(address));
+    let result = searcher.search(
+        SaltStrategy::Sequential { start },
+        max_attempts,
+        &matcher,
+        50_000,
+        Some(&checkpoint_path),
+        |total| {
+            print!("\rAttempts: {} | Searching...", total);
+            io::stdout().flush().unwrap();
+        },
+    );
+
+    if let Some(found) = result {
         pb.finish_with_message("Found!");
-        
+        let salt = H256::from(found.salt);
+        let address = Address::from(found.address);
+
         println!();
         println!("🎉 SUCCESS! Vanity address found!");
         println!("==================================");
         println!("Salt: {:#x}", salt);
         println!("Address: {:#x}", address);
-        println!("Pattern: 0x{}...{}", config.starts_with, config.ends_with);
+        println!("Pattern: 0x{}...{}", starts_with, ends_with);
         println!("Time: {:.2}s", start_time.elapsed().as_secs_f64());
-        println!("Attempts: {}", attempts.load(Ordering::Relaxed));
+        println!("Attempts: {}", found.attempts);
         println!();
         println!("📋 UPDATE YOUR DEPLOYMENT SCRIPT:");
         println!("VANITY_SALT = {:#x};", salt);
@@ -144,70 +253,6 @@ fn main() {
     }
 }
 
-fn search_batch(
-    config: &VanityConfig,
-    start: u64,
-    count: u64,
-    found: &Arc<AtomicBool>,
-    attempts: &Arc<AtomicU64>,
-) -> Option<(H256, Address)> {
-    for i in 0..count {
-        if found.load(Ordering::Relaxed) {
-            return None;
-        }
-
-        let salt_num = start + i;
-        let salt = H256::from_low_u64_be(salt_num);
-        let address = compute_create2_address(config.factory, salt, config.bytecode_hash);
-
-        attempts.fetch_add(1, Ordering::Relaxed);
-
-        if check_vanity_pattern(&address, &config.starts_with, &config.ends_with) {
-            found.store(true, Ordering::Relaxed);
-            return Some((salt, address));
-        }
-
-        // Update progress every 50k attempts
-        if i % 50_000 == 0 {
-            let total_attempts = attempts.load(Ordering::Relaxed);
-            if total_attempts % 100_000 == 0 {
-                print!("\rAttempts: {} | Searching...", total_attempts);
-                io::stdout().flush().unwrap();
-            }
-        }
-    }
-    None
-}
-
-fn compute_create2_address(factory: Address, salt: H256, bytecode_hash: H256) -> Address {
-    let mut hasher = Keccak256::new();
-    hasher.update(&[0xff]);
-    hasher.update(factory.as_bytes());
-    hasher.update(salt.as_bytes());
-    hasher.update(bytecode_hash.as_bytes());
-    
-    let hash = hasher.finalize();
-    Address::from_slice(&hash[12..])
-}
-
-fn check_vanity_pattern(address: &Address, starts_with: &str, ends_with: &str) -> bool {
-    let addr_hex = hex::encode(address.as_bytes()).to_lowercase();
-    
-    let starts_match = if starts_with.is_empty() {
-        true
-    } else {
-        addr_hex.starts_with(starts_with)
-    };
-    
-    let ends_match = if ends_with.is_empty() {
-        true
-    } else {
-        addr_hex.ends_with(ends_with)
-    };
-    
-    starts_match && ends_match
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -217,8 +262,9 @@ mod tests {
         // Test address: 0x6900000000000000000000000000000000007777
         let test_address = "6900000000000000000000000000000000007777";
         let address_bytes = hex::decode(test_address).unwrap();
-        let address = Address::from_slice(&address_bytes);
-        
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&address_bytes);
+
         assert!(check_vanity_pattern(&address, "69", "7777"));
         assert!(!check_vanity_pattern(&address, "70", "7777"));
         assert!(!check_vanity_pattern(&address, "69", "8888"));
@@ -226,13 +272,36 @@ mod tests {
 
     #[test]
     fn test_create2_computation() {
-        // Test with known values
-        let factory = "0xAA28020DDA6b954D16208eccF873D79AC6533833".parse().unwrap();
-        let salt = H256::zero();
-        let bytecode_hash = H256::zero();
-        
-        let address = compute_create2_address(factory, salt, bytecode_hash);
-        println!("Test address: {:#x}", address);
+        let factory: Address = "0xAA28020DDA6b954D16208eccF873D79AC6533833".parse().unwrap();
+        let searcher = VanitySearcher::new(*factory.as_fixed_bytes(), [0u8; 32], 1);
+        let address = searcher.address_for_salt(&[0u8; 32]);
+        println!("Test address: {:#x}", Address::from(address));
         // This should produce a deterministic address
     }
+
+    #[test]
+    fn test_matchers_compose() {
+        let test_address = "6900000000000000000000000000000000007777";
+        let address_bytes = hex::decode(test_address).unwrap();
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&address_bytes);
+
+        let modes = vec![
+            VanityMode::PrefixSuffix { starts_with: "69".to_string(), ends_with: "7777".to_string() },
+            VanityMode::Contains { pattern: "0000".to_string() },
+        ];
+        assert!(modes.iter().all(|m| m.matches(&address)));
+
+        let fails = vec![VanityMode::MinLeadingZeroBytes { min: 18 }];
+        assert!(!fails.iter().all(|m| m.matches(&address)));
+    }
+
+    #[test]
+    fn test_min_leading_zero_bytes() {
+        let bytes = hex::decode(format!("{}dead", "00".repeat(18))).unwrap();
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&bytes);
+        assert!(VanityMode::MinLeadingZeroBytes { min: 18 }.matches(&address));
+        assert!(!VanityMode::MinLeadingZeroBytes { min: 19 }.matches(&address));
+    }
 }